@@ -0,0 +1,250 @@
+use core::mem::MaybeUninit;
+
+/// A fixed-capacity sibling of [RingHeap](crate::RingHeap) for `no_std` use.
+///
+/// Backed by an inline `[MaybeUninit<T>; N]` array, it performs no heap
+/// allocation and does not depend on `alloc`, so it can be used on targets
+/// where the maximum element count is known at compile time and dynamic
+/// allocation is unavailable. As with [RingHeap](crate::RingHeap), only the
+/// `len` logical slots starting at `start` (wrapping around `N`) are ever
+/// considered initialised.
+pub struct ArrayRingHeap<T: PartialOrd, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    start: usize,
+    end: usize,
+    len: usize,
+}
+
+impl<T: PartialOrd, const N: usize> ArrayRingHeap<T, N> {
+    /// Construct an empty [ArrayRingHeap].
+    pub fn new() -> Self {
+        Self {
+            data: [const { MaybeUninit::uninit() }; N],
+            start: 0,
+            end: 0,
+            len: 0,
+        }
+    }
+
+    /// The number of live elements in the heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the heap holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the [ArrayRingHeap], dropping its live elements.
+    pub fn clear(&mut self) {
+        for i in 0..self.len {
+            let idx = self.real_idx(i);
+            unsafe {
+                self.data[idx].assume_init_drop();
+            }
+        }
+        self.start = 0;
+        self.end = 0;
+        self.len = 0;
+    }
+
+    /// Insert a new item into the [ArrayRingHeap].
+    ///
+    /// Returns `Ok(())` on success. Since the capacity is fixed at `N`, a full
+    /// heap cannot grow: when there is no room the element is handed back
+    /// unchanged as `Err(x)`.
+    pub fn insert(&mut self, x: T) -> Result<(), T> {
+        if self.len == N {
+            return Err(x);
+        }
+        self.data[self.end] = MaybeUninit::new(x);
+        self.end = (self.end + 1) % N;
+        self.len += 1;
+        // The freshly pushed element is the last logical slot; sift it up from
+        // there rather than from a physical index, which only coincided with
+        // the logical one while `start == 0`.
+        self.heapify_up(self.len - 1);
+        Ok(())
+    }
+
+    /// Returns an [Option] of the smallest item in the [ArrayRingHeap].
+    ///
+    /// The returned value is moved out of the buffer, leaving its slot
+    /// uninitialised.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len > 0 {
+            let slot = core::mem::replace(&mut self.data[self.start], MaybeUninit::uninit());
+            let x = unsafe { slot.assume_init() };
+            self.start = (self.start + 1) % N;
+            self.len -= 1;
+            // Advancing `start` shifts every remaining logical index down by
+            // one, which does not keep the heap shape, so restore it bottom-up.
+            for i in (0..self.len / 2).rev() {
+                self.heapify_down(i);
+            }
+            Some(x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the smallest item, or `None` if empty.
+    pub fn peek(&self) -> Option<&T> {
+        if self.len > 0 {
+            Some(unsafe { self.data[self.start].assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    fn real_idx(&self, i: usize) -> usize {
+        (self.start + i) % N
+    }
+
+    fn parent_idx(i: usize) -> usize {
+        (i - 1) / 2
+    }
+
+    fn left_child_idx(i: usize) -> usize {
+        1 + 2 * i
+    }
+
+    fn right_child_idx(i: usize) -> usize {
+        2 + 2 * i
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        let real_i = self.real_idx(i);
+        let real_j = self.real_idx(j);
+        self.data.swap(real_i, real_j);
+    }
+
+    fn heapify_up(&mut self, i: usize) {
+        if i == 0 {
+            return;
+        }
+        let parent_idx = Self::parent_idx(i);
+        if self.get(parent_idx) > self.get(i) {
+            self.swap(i, parent_idx);
+            self.heapify_up(parent_idx);
+        }
+    }
+
+    fn heapify_down(&mut self, i: usize) {
+        let left_child_idx = Self::left_child_idx(i);
+        let right_child_idx = Self::right_child_idx(i);
+        if i > self.len || left_child_idx >= self.len {
+            return;
+        }
+
+        let left_val = self.get(left_child_idx);
+        let this_val = self.get(i);
+        if right_child_idx >= self.len {
+            if this_val > left_val {
+                self.swap(i, left_child_idx);
+                self.heapify_down(left_child_idx);
+            }
+            return;
+        }
+
+        let right_val = self.get(right_child_idx);
+        // Pick the smaller child, allowing ties, so a parent that is larger
+        // than both children still sinks even when the children are equal.
+        let child_idx = if left_val <= right_val {
+            left_child_idx
+        } else {
+            right_child_idx
+        };
+        if self.get(i) > self.get(child_idx) {
+            self.swap(i, child_idx);
+            self.heapify_down(child_idx);
+        }
+    }
+
+    fn get(&self, i: usize) -> &T {
+        debug_assert!(i < self.len);
+        let idx = self.real_idx(i);
+        unsafe { self.data[idx].assume_init_ref() }
+    }
+}
+
+impl<T: PartialOrd, const N: usize> Default for ArrayRingHeap<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd, const N: usize> Drop for ArrayRingHeap<T, N> {
+    fn drop(&mut self) {
+        for i in 0..self.len {
+            let idx = self.real_idx(i);
+            unsafe {
+                self.data[idx].assume_init_drop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ArrayRingHeap;
+
+    #[test]
+    fn inserts_and_pops() {
+        let mut heap = ArrayRingHeap::<i32, 8>::new();
+        assert_eq!(heap.insert(2), Ok(()));
+        assert_eq!(heap.insert(1), Ok(()));
+        assert_eq!(heap.insert(4), Ok(()));
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.len(), 2);
+    }
+
+    #[test]
+    fn insert_after_pop_keeps_invariant() {
+        // Interleaving inserts and pops drives `start` off zero, so the sift-up
+        // must work on logical indices; before the fix this read uninitialised
+        // slots once `start != 0`.
+        let mut heap = ArrayRingHeap::<i32, 8>::new();
+        heap.insert(5).unwrap();
+        heap.insert(-5).unwrap();
+        heap.insert(0).unwrap();
+        assert_eq!(heap.pop(), Some(-5));
+        assert_eq!(heap.peek(), Some(&0));
+
+        heap.insert(10).unwrap();
+        heap.insert(-7).unwrap();
+        assert_eq!(heap.pop(), Some(-7));
+        assert_eq!(heap.pop(), Some(0));
+
+        heap.insert(11).unwrap();
+        heap.insert(-7).unwrap();
+        assert_eq!(heap.pop(), Some(-7));
+        assert_eq!(heap.peek(), Some(&5));
+    }
+
+    #[test]
+    fn orders_duplicate_keys() {
+        let mut heap = ArrayRingHeap::<i32, 8>::new();
+        for x in [4, 2, 4, 1, 2, 1, 4] {
+            heap.insert(x).unwrap();
+        }
+        assert_eq!(heap.peek(), Some(&1));
+
+        let mut sorted = Vec::new();
+        while let Some(x) = heap.pop() {
+            sorted.push(x);
+        }
+        assert_eq!(sorted, vec![1, 1, 2, 2, 4, 4, 4]);
+    }
+
+    #[test]
+    fn hands_back_element_when_full() {
+        let mut heap = ArrayRingHeap::<i32, 2>::new();
+        assert_eq!(heap.insert(5), Ok(()));
+        assert_eq!(heap.insert(3), Ok(()));
+        assert_eq!(heap.insert(9), Err(9));
+        assert_eq!(heap.len(), 2);
+    }
+}
@@ -1,19 +1,29 @@
 #![allow(dead_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+mod array;
+pub use array::ArrayRingHeap;
 
 /// Custom Minheap built on top of an unsafe ring buffer.
 ///
 /// As an important implementation detail when reading this code: The ring
-/// buffer part is based on [Vec], and its length is used as the ring buffer
-/// capacity.
-#[derive(Debug, Clone)]
-pub struct RingHeap<T: Copy + std::cmp::PartialOrd> {
-    data: Vec<T>,
+/// buffer part is based on a [Vec] of [MaybeUninit] slots, and that vec's
+/// length is used as the ring buffer capacity. Only the `len` logical slots
+/// starting at `start` (wrapping around the end of the buffer) are ever
+/// considered initialised; every other slot holds uninitialised memory.
+pub struct RingHeap<T: core::cmp::PartialOrd> {
+    data: Vec<MaybeUninit<T>>,
     start: usize,
     end: usize,
     len: usize,
 }
 
-impl<T: Copy + std::cmp::PartialOrd + std::fmt::Debug> RingHeap<T> {
+impl<T: core::cmp::PartialOrd> RingHeap<T> {
     /// Construct an empty [RingHeap].
     pub fn new() -> Self {
         Self::with_capacity(1)
@@ -29,10 +39,50 @@ impl<T: Copy + std::cmp::PartialOrd + std::fmt::Debug> RingHeap<T> {
         }
     }
 
+    /// Build a [RingHeap] from an existing [Vec] in O(n).
+    ///
+    /// Takes ownership of the vec as the backing store and restores the heap
+    /// invariant bottom-up using Floyd's algorithm: every internal node is
+    /// sifted down once, starting from the last parent. Because the sift work
+    /// is bounded by each node's subtree height and most nodes sit near the
+    /// leaves, this costs O(n) overall rather than the O(n log n) of inserting
+    /// the elements one at a time.
+    fn from_vec(data: Vec<T>) -> Self {
+        let len = data.len();
+        let mut data: Vec<MaybeUninit<T>> = data.into_iter().map(MaybeUninit::new).collect();
+        // Keep one spare slot, as `push` does, so `end` stays inside the buffer.
+        data.push(MaybeUninit::uninit());
+        let mut heap = Self {
+            data,
+            start: 0,
+            end: len,
+            len,
+        };
+        heap.rebuild();
+        heap
+    }
+
+    /// Build a [RingHeap] from a slice in O(n).
+    ///
+    /// Clones the slice into a fresh backing store and heapifies it in place;
+    /// see [RingHeap::from_vec] for the bottom-up construction details.
+    pub fn from_slice(slice: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(slice.to_vec())
+    }
+
     /// Clears the [RingHeap].
     ///
-    /// Does not actually drop the data, but makes it inaccessible.
+    /// Drops the live elements and resets the buffer to empty.
     pub fn clear(&mut self) {
+        for i in 0..self.len {
+            let idx = self.real_idx(i);
+            unsafe {
+                self.data[idx].assume_init_drop();
+            }
+        }
         self.start = 0;
         self.end = 0;
         self.len = 0;
@@ -41,12 +91,10 @@ impl<T: Copy + std::cmp::PartialOrd + std::fmt::Debug> RingHeap<T> {
     /// Insert a new item into the [RingHeap]
     pub fn insert(&mut self, x: T) {
         self.push(x);
-        let idx = if self.end == 0 {
-            self.data.len() - 1
-        } else {
-            self.end - 1
-        };
-        self.heapify_up(idx);
+        // The freshly pushed element is the last logical slot; sift it up from
+        // there rather than from a physical index, which only coincided with
+        // the logical one while `start == 0`.
+        self.heapify_up(self.len - 1);
     }
 
     /// Returns an [Option] of the smallest item in the [RingHeap].
@@ -54,11 +102,12 @@ impl<T: Copy + std::cmp::PartialOrd + std::fmt::Debug> RingHeap<T> {
     /// Returns a `Some(x)`, where `x` is that smallest item, if the heap is not
     /// empty, and a `None` otherwise.
     ///
-    /// Note that the piece of data is simply being copied and not actually
-    /// removed from the heap; its just being made inaccessible.
+    /// The returned value is moved out of the buffer, leaving its slot
+    /// uninitialised.
     pub fn pop(&mut self) -> Option<T> {
         if self.len > 0 {
-            let x = self.data[self.start];
+            let slot = core::mem::replace(&mut self.data[self.start], MaybeUninit::uninit());
+            let x = unsafe { slot.assume_init() };
             self.start = (self.start + 1) % self.data.len();
             self.len -= 1;
             self.heapify_down(0);
@@ -68,18 +117,137 @@ impl<T: Copy + std::cmp::PartialOrd + std::fmt::Debug> RingHeap<T> {
         }
     }
 
+    /// Consume the [RingHeap], returning its elements in arbitrary heap order.
+    ///
+    /// The elements come out in the order they happen to sit in the backing
+    /// store; no ordering guarantee beyond "these are the live elements" is
+    /// made. Use [RingHeap::into_sorted_vec] when you need them sorted.
+    pub fn into_vec(mut self) -> Vec<T> {
+        let n = self.len;
+        let out: Vec<T> = (0..n).map(|i| self.take(i)).collect();
+        self.len = 0;
+        out
+    }
+
+    /// Consume the [RingHeap], returning its elements in ascending order.
+    ///
+    /// This is an in-place heapsort: the root (the current minimum) is swapped
+    /// with the last logical element, the logical length is shrunk, and the new
+    /// root is sifted down. Each step parks the next-smallest element in a
+    /// sorted region growing from the tail; since this is a min-heap that region
+    /// ends up descending, so it is reversed on the way out. Runs in O(n log n).
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let n = self.len;
+        for end in (1..n).rev() {
+            self.swap(0, end);
+            self.len -= 1;
+            self.heapify_down(0);
+        }
+        let mut out: Vec<T> = (0..n).map(|i| self.take(i)).collect();
+        out.reverse();
+        self.len = 0;
+        out
+    }
+
+    /// Insert `x` while keeping only the `k` smallest elements seen so far.
+    ///
+    /// This turns the heap into a streaming top-K (K-smallest) selector over an
+    /// unbounded input: while fewer than `k` elements are held the item is
+    /// simply inserted; once the heap is full, an arriving item larger than the
+    /// current maximum is dropped, otherwise the maximum is evicted to make
+    /// room. The element that did not make the cut — either `x` itself or the
+    /// evicted old maximum — is returned.
+    ///
+    /// Because this is a min-heap the maximum lives among the leaves, so
+    /// locating it is an O(k) leaf scan and the subsequent rebuild is O(k);
+    /// maintaining a secondary max index could bring the hot path down to
+    /// O(log k) at the cost of extra bookkeeping, which is not worth it for the
+    /// small `k` this mode targets.
+    pub fn insert_capped(&mut self, k: usize, x: T) -> Option<T> {
+        if k == 0 {
+            return Some(x);
+        }
+        if self.len < k {
+            self.insert(x);
+            return None;
+        }
+        let m = self.max_leaf();
+        if &x > self.get(m) {
+            return Some(x);
+        }
+        let old = self.take(m);
+        let idx = self.real_idx(m);
+        self.data[idx] = MaybeUninit::new(x);
+        self.rebuild();
+        Some(old)
+    }
+
+    /// Logical index of the largest element, found by scanning the leaves.
+    fn max_leaf(&self) -> usize {
+        let first_leaf = self.len / 2;
+        let mut m = first_leaf;
+        for i in (first_leaf + 1)..self.len {
+            if self.get(i) > self.get(m) {
+                m = i;
+            }
+        }
+        m
+    }
+
+    /// Restore the heap invariant over the whole logical array bottom-up.
+    fn rebuild(&mut self) {
+        if self.len > 1 {
+            for i in (0..=(self.len / 2 - 1)).rev() {
+                self.heapify_down(i);
+            }
+        }
+    }
+
+    /// Returns a mutable guard over the smallest element, or `None` if empty.
+    ///
+    /// The returned [PeekMut] derefs to the current minimum and lets it be
+    /// mutated in place; when the guard is dropped (or [PeekMut::pop] is called)
+    /// the heap invariant is restored by sifting the root back down. This is the
+    /// efficient primitive for algorithms such as Dijkstra, where the priority
+    /// of the top element is updated and resifted without a pop-then-push round
+    /// trip.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+        if self.len > 0 {
+            Some(PeekMut { heap: self })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the live elements in arbitrary heap order.
+    ///
+    /// Walks the `len` logical slots through [RingHeap::real_idx], so it yields
+    /// references into the backing store without regard to priority order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.len).map(move |i| self.get(i))
+    }
+
+    /// Drain the heap, yielding its elements in ascending (priority) order.
+    ///
+    /// Leaves the heap empty. The elements come out smallest-first, the same
+    /// order repeated [RingHeap::pop] calls would produce.
+    pub fn drain(&mut self) -> alloc::vec::IntoIter<T> {
+        let taken = core::mem::take(self);
+        taken.into_sorted_vec().into_iter()
+    }
+
     fn push(&mut self, x: T) {
         if self.len + 1 >= self.data.len() {
-            self.grow(x);
+            self.grow();
         }
-        self.data[self.end] = x;
+        self.data[self.end] = MaybeUninit::new(x);
         self.end = (self.end + 1) % self.data.len();
         self.len += 1;
     }
 
-    fn peek(&self) -> Option<T> {
+    fn peek(&self) -> Option<&T> {
         if self.len > 0 {
-            Some(self.data[self.start])
+            Some(unsafe { self.data[self.start].assume_init_ref() })
         } else {
             None
         }
@@ -108,7 +276,7 @@ impl<T: Copy + std::cmp::PartialOrd + std::fmt::Debug> RingHeap<T> {
     }
 
     fn heapify_up(&mut self, i: usize) {
-        if i == self.start {
+        if i == 0 {
             return;
         }
         let parent_idx = Self::parent_idx(i);
@@ -136,44 +304,224 @@ impl<T: Copy + std::cmp::PartialOrd + std::fmt::Debug> RingHeap<T> {
         }
 
         let right_val = self.get(right_child_idx);
-        if left_val > right_val && this_val > right_val {
-            self.swap(i, right_child_idx);
-            self.heapify_down(right_child_idx);
-        } else if right_val > left_val && this_val > left_val {
-            self.swap(i, left_child_idx);
-            self.heapify_down(left_child_idx);
+        // Pick the smaller child, allowing ties, so a parent that is larger
+        // than both children still sinks even when the children are equal.
+        let child_idx = if left_val <= right_val {
+            left_child_idx
+        } else {
+            right_child_idx
+        };
+        if self.get(i) > self.get(child_idx) {
+            self.swap(i, child_idx);
+            self.heapify_down(child_idx);
         }
     }
 
     fn set(&mut self, i: usize, x: T) {
         debug_assert!(i < self.len);
         let idx = self.real_idx(i);
-        self.data[idx] = x;
+        unsafe {
+            self.data[idx].assume_init_drop();
+        }
+        self.data[idx] = MaybeUninit::new(x);
     }
 
-    fn get(&self, i: usize) -> T {
+    fn get(&self, i: usize) -> &T {
         debug_assert!(i < self.len);
-        self.data[self.real_idx(i)]
+        let idx = self.real_idx(i);
+        unsafe { self.data[idx].assume_init_ref() }
     }
 
-    fn grow(&mut self, x: T) {
+    /// Move the value at logical index `i` out of the buffer, leaving its slot
+    /// uninitialised. The caller is responsible for not reading `i` again.
+    fn take(&mut self, i: usize) -> T {
+        let idx = self.real_idx(i);
+        unsafe { core::mem::replace(&mut self.data[idx], MaybeUninit::uninit()).assume_init() }
+    }
+
+    fn grow(&mut self) {
         let old_len = self.data.len();
-        let new_len = usize::max(2, 2 * self.data.len());
+        let new_len = usize::max(2, 2 * old_len);
 
-        // NOTE: This fills up the vec with junk data!
-        // The function argument is simply used to provide that junk data so
-        // the vec entries are initialised.
-        self.data.resize(new_len, x);
+        self.data.reserve(new_len - old_len);
+        for _ in old_len..new_len {
+            self.data.push(MaybeUninit::uninit());
+        }
         if self.start > self.end {
             let n_to_move = old_len - self.start;
             let new_start = new_len - n_to_move;
-            self.data
-                .copy_within(self.start..self.start + n_to_move, new_start);
+            for k in (0..n_to_move).rev() {
+                let slot = core::mem::replace(
+                    &mut self.data[self.start + k],
+                    MaybeUninit::uninit(),
+                );
+                self.data[new_start + k] = slot;
+            }
             self.start = new_start;
         }
     }
 }
 
+impl<T: core::cmp::PartialOrd> Default for RingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: core::cmp::PartialOrd> Drop for RingHeap<T> {
+    fn drop(&mut self) {
+        for i in 0..self.len {
+            let idx = self.real_idx(i);
+            unsafe {
+                self.data[idx].assume_init_drop();
+            }
+        }
+    }
+}
+
+impl<T: core::cmp::PartialOrd + Clone> Clone for RingHeap<T> {
+    fn clone(&self) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for _ in 0..self.data.len() {
+            data.push(MaybeUninit::uninit());
+        }
+        for i in 0..self.len {
+            let idx = self.real_idx(i);
+            data[idx] = MaybeUninit::new(self.get(i).clone());
+        }
+        Self {
+            data,
+            start: self.start,
+            end: self.end,
+            len: self.len,
+        }
+    }
+}
+
+impl<T: core::cmp::PartialOrd + core::fmt::Debug> core::fmt::Debug for RingHeap<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RingHeap")
+            .field(
+                "data",
+                &(0..self.len).map(|i| self.get(i)).collect::<Vec<_>>(),
+            )
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// A mutable guard over the minimum element of a [RingHeap].
+///
+/// Obtained from [RingHeap::peek_mut]. Dereferencing yields the current
+/// minimum; dropping the guard sifts the (possibly changed) root back down so
+/// the heap invariant holds again.
+pub struct PeekMut<'a, T: core::cmp::PartialOrd> {
+    heap: &'a mut RingHeap<T>,
+}
+
+impl<T: core::cmp::PartialOrd> PeekMut<'_, T> {
+    /// Remove the minimum element and return it, consuming the guard.
+    pub fn pop(this: Self) -> T {
+        // `RingHeap::pop` already re-sifts, so suppress the guard's own drop to
+        // avoid a second, redundant `heapify_down`.
+        let mut this = core::mem::ManuallyDrop::new(this);
+        this.heap.pop().unwrap()
+    }
+}
+
+impl<T: core::cmp::PartialOrd> core::ops::Deref for PeekMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        let idx = self.heap.start;
+        unsafe { self.heap.data[idx].assume_init_ref() }
+    }
+}
+
+impl<T: core::cmp::PartialOrd> core::ops::DerefMut for PeekMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        let idx = self.heap.start;
+        unsafe { self.heap.data[idx].assume_init_mut() }
+    }
+}
+
+impl<T: core::cmp::PartialOrd> Drop for PeekMut<'_, T> {
+    fn drop(&mut self) {
+        self.heap.heapify_down(0);
+    }
+}
+
+impl<T: core::cmp::PartialOrd> From<Vec<T>> for RingHeap<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec(data)
+    }
+}
+
+impl<T: core::cmp::PartialOrd> IntoIterator for RingHeap<T> {
+    type Item = T;
+    type IntoIter = alloc::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<T: core::cmp::PartialOrd> Extend<T> for RingHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        if lower > self.len {
+            // A large batch is cheaper to fold in with a single O(n) rebuild
+            // than with repeated O(log n) inserts, so collect everything and
+            // heapify once.
+            let mut all: Vec<T> = Vec::with_capacity(self.len + lower);
+            for i in 0..self.len {
+                all.push(self.take(i));
+            }
+            self.start = 0;
+            self.end = 0;
+            self.len = 0;
+            all.extend(iter);
+            *self = Self::from_vec(all);
+        } else {
+            for x in iter {
+                self.insert(x);
+            }
+        }
+    }
+}
+
+impl<T: core::cmp::PartialOrd> FromIterator<T> for RingHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: core::cmp::PartialOrd + serde::Serialize> serde::Serialize for RingHeap<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Only the live elements are serialized, as a flat sequence; the
+        // internal start/end/capacity bookkeeping is intentionally left out so
+        // the wire format is independent of it.
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: core::cmp::PartialOrd + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for RingHeap<T>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Rebuild from scratch with the O(n) bottom-up heapify rather than
+        // trusting the incoming order, so the heap invariant holds regardless
+        // of what was deserialized.
+        let data = Vec::<T>::deserialize(deserializer)?;
+        Ok(Self::from_vec(data))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::RingHeap;
@@ -193,21 +541,21 @@ mod test {
         assert_eq!(heap.len, 1);
         assert_eq!(heap.start, 0);
         assert_eq!(heap.end, 1);
-        assert_eq!(heap.peek(), Some(2));
+        assert_eq!(heap.peek(), Some(&2));
 
         heap.insert(1);
         assert_eq!(heap.data.len(), 4);
         assert_eq!(heap.len, 2);
         assert_eq!(heap.start, 0);
         assert_eq!(heap.end, 2);
-        assert_eq!(heap.peek(), Some(1));
+        assert_eq!(heap.peek(), Some(&1));
 
         heap.insert(4);
         assert_eq!(heap.data.len(), 4);
         assert_eq!(heap.len, 3);
         assert_eq!(heap.start, 0);
         assert_eq!(heap.end, 3);
-        assert_eq!(heap.peek(), Some(1));
+        assert_eq!(heap.peek(), Some(&1));
     }
 
     #[test]
@@ -225,7 +573,7 @@ mod test {
         assert_eq!(heap.start, 0);
         assert_eq!(heap.end, 7);
 
-        // NOTE: pop shifts .start upwards, without touching the .data or .end
+        // NOTE: pop shifts .start upwards, without touching the .end
         assert_eq!(heap.pop(), Some(-3));
         assert_eq!(heap.len, 6);
         assert_eq!(heap.start, 1);
@@ -269,13 +617,170 @@ mod test {
         assert_eq!(heap.data.len(), 8);
     }
 
+    #[test]
+    fn builds_from_vec() {
+        let mut heap = RingHeap::from(vec![5, 3, 8, 1, 9, 2, 7]);
+        assert_eq!(heap.len, 7);
+        assert_eq!(heap.start, 0);
+        assert_eq!(heap.peek(), Some(&1));
+
+        let mut sorted = Vec::new();
+        while let Some(x) = heap.pop() {
+            sorted.push(x);
+        }
+        assert_eq!(sorted, vec![1, 2, 3, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn builds_from_slice_and_iter() {
+        let from_slice = RingHeap::from_slice(&[4, 2, 6, 1]);
+        let from_iter: RingHeap<i32> = [4, 2, 6, 1].into_iter().collect();
+        assert_eq!(from_slice.peek(), Some(&1));
+        assert_eq!(from_iter.peek(), Some(&1));
+    }
+
+    #[test]
+    fn builds_with_duplicate_keys() {
+        // Bottom-up construction must yield a valid heap even when keys repeat,
+        // so the root is a genuine minimum on every path.
+        let heap = RingHeap::from(vec![3, 1, 3, 1, 2, 2]);
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.into_sorted_vec(), vec![1, 1, 2, 2, 3, 3]);
+
+        let from_slice = RingHeap::from_slice(&[2, 2, 2, 2]);
+        assert_eq!(from_slice.peek(), Some(&2));
+        let from_iter: RingHeap<i32> = [5, 5, 1, 5, 1].into_iter().collect();
+        assert_eq!(from_iter.peek(), Some(&1));
+    }
+
+    #[test]
+    fn drains_sorted() {
+        let heap = RingHeap::from(vec![5, 3, 8, 1, 9, 2, 7]);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn sorts_with_duplicate_keys() {
+        assert_eq!(RingHeap::from(vec![2, 1, 1]).into_sorted_vec(), vec![1, 1, 2]);
+        let heap = RingHeap::from(vec![4, 2, 4, 1, 2, 1, 4]);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 1, 2, 2, 4, 4, 4]);
+    }
+
+    #[test]
+    fn into_vec_keeps_all_elements() {
+        let heap = RingHeap::from(vec![5, 3, 8, 1]);
+        let mut v = heap.into_vec();
+        v.sort();
+        assert_eq!(v, vec![1, 3, 5, 8]);
+    }
+
+    #[test]
+    fn keeps_k_smallest() {
+        let mut heap = RingHeap::<i32>::new();
+        for x in [5, 1, 8, 3, 9, 2, 7] {
+            heap.insert_capped(3, x);
+        }
+        assert_eq!(heap.len, 3);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn keeps_k_smallest_with_duplicates() {
+        let mut heap = RingHeap::<i32>::new();
+        for x in [3, 1, 3, 2, 1, 4, 2, 1, 5] {
+            heap.insert_capped(4, x);
+        }
+        assert_eq!(heap.len, 4);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn peek_mut_resifts_on_drop() {
+        let mut heap = RingHeap::from(vec![1, 5, 3]);
+        {
+            let mut top = heap.peek_mut().unwrap();
+            assert_eq!(*top, 1);
+            *top = 10;
+        }
+        assert_eq!(heap.peek(), Some(&3));
+    }
+
+    #[test]
+    fn peek_mut_pop() {
+        use super::PeekMut;
+        let mut heap = RingHeap::from(vec![1, 5, 3]);
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(PeekMut::pop(top), 1);
+        assert_eq!(heap.peek(), Some(&3));
+    }
+
+    #[test]
+    fn iter_visits_all_elements() {
+        let heap = RingHeap::from(vec![3, 1, 2]);
+        let mut v: Vec<i32> = heap.iter().copied().collect();
+        v.sort();
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn into_iter_yields_all_elements() {
+        let heap = RingHeap::from(vec![3, 1, 2]);
+        let mut v: Vec<i32> = heap.into_iter().collect();
+        v.sort();
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn drain_empties_in_order() {
+        let mut heap = RingHeap::from(vec![3, 1, 2]);
+        let v: Vec<i32> = heap.drain().collect();
+        assert_eq!(v, vec![1, 2, 3]);
+        assert_eq!(heap.len, 0);
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn drain_empties_in_order_with_duplicates() {
+        let mut heap = RingHeap::from(vec![3, 1, 2, 1, 3, 2]);
+        let v: Vec<i32> = heap.drain().collect();
+        assert_eq!(v, vec![1, 1, 2, 2, 3, 3]);
+        assert_eq!(heap.len, 0);
+    }
+
+    #[test]
+    fn extend_folds_in_batch() {
+        let mut heap = RingHeap::from(vec![5, 2]);
+        heap.extend([1, 9, 3, 0, 4]);
+        assert_eq!(heap.peek(), Some(&0));
+        assert_eq!(heap.into_sorted_vec(), vec![0, 1, 2, 3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn holds_non_copy_types() {
+        let mut heap = RingHeap::<String>::new();
+        heap.insert("pear".to_string());
+        heap.insert("apple".to_string());
+        heap.insert("orange".to_string());
+        assert_eq!(heap.peek(), Some(&"apple".to_string()));
+        assert_eq!(heap.pop(), Some("apple".to_string()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let heap = RingHeap::from(vec![5, 3, 8, 1, 9, 2, 7]);
+        let json = serde_json::to_string(&heap).unwrap();
+        let restored: RingHeap<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.into_sorted_vec(), vec![1, 2, 3, 5, 7, 8, 9]);
+    }
+
     #[test]
     fn complex_example() {
         let mut heap = RingHeap::<i32>::new();
         heap.insert(12);
-        assert_eq!(heap.peek(), Some(12));
+        assert_eq!(heap.peek(), Some(&12));
         heap.insert(10);
-        assert_eq!(heap.peek(), Some(10));
+        assert_eq!(heap.peek(), Some(&10));
         assert_eq!(heap.pop(), Some(10));
         assert_eq!(heap.pop(), Some(12));
         assert_eq!(heap.pop(), None);
@@ -284,7 +789,7 @@ mod test {
         heap.insert(-5);
         heap.insert(0);
         assert_eq!(heap.pop(), Some(-5));
-        assert_eq!(heap.peek(), Some(0));
+        assert_eq!(heap.peek(), Some(&0));
 
         heap.insert(10);
         heap.insert(-7);
@@ -294,6 +799,6 @@ mod test {
         heap.insert(11);
         heap.insert(-7);
         assert_eq!(heap.pop(), Some(-7));
-        assert_eq!(heap.peek(), Some(5));
+        assert_eq!(heap.peek(), Some(&5));
     }
 }